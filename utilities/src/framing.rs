@@ -0,0 +1,779 @@
+//! Length-prefixed, checksummed framing for `ToBytes` and `FromBytes` objects.
+//!
+//! A frame is laid out as `variable_length_integer(len) || payload || crc32(payload)`,
+//! where the checksum is written as a little-endian `u32`. The length is computed
+//! with a counting pass over `ToBytes::write`, so large objects (e.g. proving keys)
+//! are streamed directly into the writer rather than staged in memory first.
+//!
+//! This module is enabled by the `framing` feature, and its async adapters by the
+//! `async` feature. Their tests only run with those features enabled; run all of
+//! them with `cargo test -p snarkvm-utilities --features snarkvm-utilities/async`.
+
+use crate::{
+    bytes::{FromBytes, ToBytes},
+    error,
+    variable_length_integer::variable_length_integer,
+};
+
+use crc32fast::Hasher;
+use std::io::{self, BufReader, BufWriter, Read, Result as IoResult, Write};
+
+/// A writer that discards its input, recording only the number of bytes written.
+#[derive(Debug, Default)]
+pub struct CountingWriter {
+    count: u64,
+}
+
+impl CountingWriter {
+    /// Creates a writer that has counted zero bytes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl Write for CountingWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.count += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// A writer that forwards to `W` while counting and computing a CRC-32 of every byte written.
+#[derive(Debug)]
+pub struct ChecksumWriter<W: Write> {
+    writer: W,
+    hasher: Hasher,
+    count: u64,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    /// Creates a writer that forwards every byte to `writer`, starting from an
+    /// empty checksum and a count of zero.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            hasher: Hasher::new(),
+            count: 0,
+        }
+    }
+
+    /// Returns the checksum of the bytes written so far.
+    pub fn checksum(&self) -> u32 {
+        self.hasher.clone().finalize()
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let written = self.writer.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> IoResult<()> {
+        self.writer.flush()
+    }
+}
+
+/// A reader that yields at most `limit` bytes from `R` while computing a CRC-32
+/// of every byte read.
+#[derive(Debug)]
+pub struct ChecksumReader<R: Read> {
+    reader: R,
+    hasher: Hasher,
+    remaining: u64,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    /// Creates a reader that yields at most `limit` bytes from `reader`, starting
+    /// from an empty checksum. Once `limit` bytes have been read, every further
+    /// read returns `Ok(0)` (EOF) without touching `reader`.
+    pub fn new(reader: R, limit: u64) -> Self {
+        Self {
+            reader,
+            hasher: Hasher::new(),
+            remaining: limit,
+        }
+    }
+
+    /// Returns the checksum of the bytes read so far.
+    pub fn checksum(&self) -> u32 {
+        self.hasher.clone().finalize()
+    }
+
+    /// Returns the number of bytes that may still be read before the limit is reached.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        if self.remaining == 0 {
+            return Ok(0);
+        }
+
+        let max = std::cmp::min(buf.len() as u64, self.remaining) as usize;
+        let read = self.reader.read(&mut buf[..max])?;
+        self.hasher.update(&buf[..read]);
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+/// Returns the number of bytes `value` occupies when serialized with `ToBytes`.
+pub fn serialized_size<T: ToBytes + ?Sized>(value: &T) -> IoResult<u64> {
+    let mut counter = CountingWriter::new();
+    value.write(&mut counter)?;
+    Ok(counter.count())
+}
+
+/// Writes `value` into `writer` as a length-prefixed, checksummed frame.
+///
+/// Fails if `value` serializes to a different length than its first, counting
+/// pass reported; the partially written frame must then be discarded.
+pub fn write_framed<T: ToBytes + ?Sized, W: Write>(value: &T, mut writer: W) -> IoResult<()> {
+    let length = serialized_size(value)?;
+    writer.write_all(&variable_length_integer(length))?;
+
+    let mut checksum_writer = ChecksumWriter::new(&mut writer);
+    value.write(&mut checksum_writer)?;
+    if checksum_writer.count() != length {
+        return Err(error("Frame length does not match the serialized size"));
+    }
+    let checksum = checksum_writer.checksum();
+
+    writer.write_all(&checksum.to_le_bytes())
+}
+
+/// Reads a length-prefixed, checksummed frame from `reader`, failing if the
+/// payload is longer than `max_length`, does not match its checksum, or is not
+/// consumed exactly by `T::read`.
+///
+/// The rest of the frame is consumed before reporting a checksum mismatch, a
+/// `T::read` failure, or trailing bytes, so `reader` stays aligned to the next
+/// frame. After an I/O error or an oversized length, the position of `reader`
+/// is unspecified and it should not be reused.
+pub fn read_framed<T: FromBytes, R: Read>(mut reader: R, max_length: u64) -> IoResult<T> {
+    let length = read_frame_length(&mut reader)?;
+    if length > max_length {
+        return Err(error("Frame length exceeds the maximum"));
+    }
+
+    read_frame_body(reader, length)
+}
+
+/// Reads the `length`-byte payload and the checksum of a frame from `reader`.
+fn read_frame_body<T: FromBytes, R: Read>(mut reader: R, length: u64) -> IoResult<T> {
+    let mut checksum_reader = ChecksumReader::new(&mut reader, length);
+    let value = T::read(&mut checksum_reader);
+    let trailing = io::copy(&mut checksum_reader, &mut io::sink())?;
+    let checksum = checksum_reader.checksum();
+
+    let mut expected = [0u8; 4];
+    reader.read_exact(&mut expected)?;
+    if checksum != u32::from_le_bytes(expected) {
+        return Err(error("Frame checksum mismatch"));
+    }
+
+    match (value?, trailing) {
+        (value, 0) => Ok(value),
+        _ => Err(error("Frame contains trailing bytes")),
+    }
+}
+
+/// Reads the variable length integer prefixing a frame.
+///
+/// Unlike `read_variable_length_integer`, this reads with `read_exact`, so it
+/// tolerates short reads, fails at EOF, and decodes lengths as `u64` on every target.
+fn read_frame_length<R: Read>(mut reader: R) -> IoResult<u64> {
+    let mut flag = [0u8; 1];
+    reader.read_exact(&mut flag)?;
+
+    let size = match flag[0] {
+        0..=252 => return Ok(flag[0] as u64),
+        0xfd => 2,
+        0xfe => 4,
+        _ => 8,
+    };
+
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes[..size])?;
+    decode_frame_length(size, bytes)
+}
+
+/// Decodes the `size` little-endian bytes of a variable length integer,
+/// rejecting non-minimal encodings as `read_variable_length_integer` does.
+fn decode_frame_length(size: usize, bytes: [u8; 8]) -> IoResult<u64> {
+    let value = u64::from_le_bytes(bytes);
+    match variable_length_integer(value).len() == size + 1 {
+        true => Ok(value),
+        false => Err(error("Invalid variable size integer")),
+    }
+}
+
+/// Writes `value` into `writer` through a `BufWriter`, coalescing the many small
+/// writes that `ToBytes` implementations typically issue.
+pub fn write_buffered<T: ToBytes + ?Sized, W: Write>(value: &T, writer: W) -> IoResult<()> {
+    let mut writer = BufWriter::new(writer);
+    value.write(&mut writer)?;
+    writer.flush()
+}
+
+/// Reads a `T` from `reader` through a `BufReader`, coalescing the many small
+/// reads that `FromBytes` implementations typically issue.
+///
+/// The `BufReader` may read ahead, so `reader` should not be reused afterwards.
+pub fn read_buffered<T: FromBytes, R: Read>(reader: R) -> IoResult<T> {
+    T::read(BufReader::new(reader))
+}
+
+#[cfg(feature = "async")]
+pub use self::asynchronous::*;
+
+#[cfg(feature = "async")]
+mod asynchronous {
+    use super::*;
+
+    use std::{cmp, mem, sync::Arc};
+    use tokio::{
+        io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+        sync::mpsc::{channel, Receiver, Sender},
+        task::{spawn_blocking, JoinError},
+    };
+
+    /// The size of the chunks passed between the blocking and async halves of a frame transfer.
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    /// The number of chunks that may be queued between the two halves, bounding memory use.
+    const CHUNK_CAPACITY: usize = 4;
+
+    /// Writes `value` into the async `writer` as a length-prefixed, checksummed frame.
+    ///
+    /// `ToBytes` is synchronous, so `value` is serialized on a blocking task and
+    /// streamed to `writer` in fixed-size chunks; at most a few chunks are held
+    /// in memory at once. Must be called from within a tokio runtime.
+    pub async fn write_framed_async<T, W>(value: Arc<T>, writer: &mut W) -> IoResult<()>
+    where
+        T: ToBytes + Send + Sync + ?Sized + 'static,
+        W: AsyncWrite + Unpin,
+    {
+        let (sender, mut receiver) = channel(CHUNK_CAPACITY);
+        let task = spawn_blocking(move || {
+            let mut channel_writer = ChannelWriter::new(sender);
+            write_framed(&*value, &mut channel_writer)?;
+            channel_writer.flush()
+        });
+
+        while let Some(chunk) = receiver.recv().await {
+            if let Err(error) = writer.write_all(&chunk).await {
+                // Closing the channel stops the serialization task.
+                drop(receiver);
+                let _ = task.await;
+                return Err(error);
+            }
+        }
+
+        join(task.await)?;
+        writer.flush().await
+    }
+
+    /// Reads a length-prefixed, checksummed frame from the async `reader`,
+    /// with the same checks and error semantics as `read_framed`.
+    ///
+    /// `FromBytes` is synchronous, so `T` is deserialized on a blocking task fed
+    /// with fixed-size chunks of the frame; at most a few chunks are held in
+    /// memory at once. Must be called from within a tokio runtime.
+    pub async fn read_framed_async<T, R>(reader: &mut R, max_length: u64) -> IoResult<T>
+    where
+        T: FromBytes + Send + 'static,
+        R: AsyncRead + Unpin,
+    {
+        let length = read_frame_length_async(reader).await?;
+        if length > max_length {
+            return Err(error("Frame length exceeds the maximum"));
+        }
+
+        let (sender, receiver) = channel(CHUNK_CAPACITY);
+        let task = spawn_blocking(move || read_frame_body(ChannelReader::new(receiver), length));
+
+        // Forward the payload and checksum, and nothing past them, to the deserialization task.
+        // Each chunk is filled to `CHUNK_SIZE` bytes (or the end of the frame) before it is sent.
+        let mut remaining = length
+            .checked_add(4)
+            .ok_or_else(|| error("Frame length exceeds the maximum"))?;
+        let mut chunk = Vec::with_capacity(cmp::min(remaining, CHUNK_SIZE as u64) as usize);
+        while remaining > 0 {
+            let limit = cmp::min(remaining, (chunk.capacity() - chunk.len()) as u64);
+            let read = (&mut *reader).take(limit).read_buf(&mut chunk).await?;
+            if read == 0 {
+                // The task observes the closed channel as an unexpected EOF.
+                break;
+            }
+            remaining -= read as u64;
+
+            if chunk.len() == chunk.capacity() || remaining == 0 {
+                let next = Vec::with_capacity(cmp::min(remaining, CHUNK_SIZE as u64) as usize);
+                if sender.send(mem::replace(&mut chunk, next)).await.is_err() {
+                    break;
+                }
+            }
+        }
+        drop(sender);
+
+        join(task.await)
+    }
+
+    /// Returns the result of a blocking task, resuming its panic if it panicked.
+    fn join<T>(result: Result<IoResult<T>, JoinError>) -> IoResult<T> {
+        match result {
+            Ok(result) => result,
+            Err(join_error) if join_error.is_panic() => std::panic::resume_unwind(join_error.into_panic()),
+            Err(_) => Err(error("Frame task was cancelled")),
+        }
+    }
+
+    /// A writer that sends its input over a bounded channel in `CHUNK_SIZE` chunks.
+    struct ChannelWriter {
+        sender: Sender<Vec<u8>>,
+        buffer: Vec<u8>,
+    }
+
+    impl ChannelWriter {
+        fn new(sender: Sender<Vec<u8>>) -> Self {
+            Self {
+                sender,
+                buffer: Vec::with_capacity(CHUNK_SIZE),
+            }
+        }
+    }
+
+    impl Write for ChannelWriter {
+        fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+            let amount = cmp::min(buf.len(), CHUNK_SIZE - self.buffer.len());
+            self.buffer.extend_from_slice(&buf[..amount]);
+            if self.buffer.len() == CHUNK_SIZE {
+                self.flush()?;
+            }
+            Ok(amount)
+        }
+
+        fn flush(&mut self) -> IoResult<()> {
+            if self.buffer.is_empty() {
+                return Ok(());
+            }
+
+            let chunk = mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+            self.sender
+                .blocking_send(chunk)
+                .map_err(|_| error("Frame writer was closed"))
+        }
+    }
+
+    /// A reader that receives its input over a bounded channel, reporting EOF once the channel closes.
+    struct ChannelReader {
+        receiver: Receiver<Vec<u8>>,
+        chunk: Vec<u8>,
+        position: usize,
+    }
+
+    impl ChannelReader {
+        fn new(receiver: Receiver<Vec<u8>>) -> Self {
+            Self {
+                receiver,
+                chunk: vec![],
+                position: 0,
+            }
+        }
+    }
+
+    impl Read for ChannelReader {
+        fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+            while self.position == self.chunk.len() {
+                match self.receiver.blocking_recv() {
+                    Some(chunk) => {
+                        self.chunk = chunk;
+                        self.position = 0;
+                    }
+                    None => return Ok(0),
+                }
+            }
+
+            let amount = cmp::min(buf.len(), self.chunk.len() - self.position);
+            buf[..amount].copy_from_slice(&self.chunk[self.position..self.position + amount]);
+            self.position += amount;
+            Ok(amount)
+        }
+    }
+
+    async fn read_frame_length_async<R: AsyncRead + Unpin>(reader: &mut R) -> IoResult<u64> {
+        let size = match reader.read_u8().await? {
+            flag @ 0..=252 => return Ok(flag as u64),
+            0xfd => 2,
+            0xfe => 4,
+            _ => 8,
+        };
+
+        let mut bytes = [0u8; 8];
+        reader.read_exact(&mut bytes[..size]).await?;
+
+        decode_frame_length(size, bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAX_LENGTH: u64 = 1 << 20;
+
+    #[test]
+    fn test_serialized_size() {
+        assert_eq!(serialized_size(&[7u64; 32]).unwrap(), 256);
+        assert_eq!(serialized_size(&()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_framed_round_trip() {
+        let expected = [42u64; 32];
+
+        let mut frame = vec![];
+        write_framed(&expected, &mut frame).unwrap();
+        assert_eq!(frame.len(), 3 + 256 + 4);
+
+        let candidate: [u64; 32] = read_framed(&frame[..], MAX_LENGTH).unwrap();
+        assert_eq!(&expected[..], &candidate[..]);
+    }
+
+    #[test]
+    fn test_framed_sequence() {
+        let mut frames = vec![];
+        write_framed(&1u32, &mut frames).unwrap();
+        write_framed(&[2u8; 16], &mut frames).unwrap();
+
+        let mut reader = &frames[..];
+        assert_eq!(read_framed::<u32, _>(&mut reader, MAX_LENGTH).unwrap(), 1);
+        assert_eq!(read_framed::<[u8; 16], _>(&mut reader, MAX_LENGTH).unwrap(), [2u8; 16]);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_framed_sequence_after_error() {
+        let mut frames = vec![];
+        write_framed(&[1u8; 32], &mut frames).unwrap();
+        write_framed(&[2u8; 32], &mut frames).unwrap();
+        write_framed(&true, &mut frames).unwrap();
+        write_framed(&[3u8; 32], &mut frames).unwrap();
+        frames[10] ^= 1;
+
+        // A checksum mismatch, trailing bytes, and a failed `T::read` each skip their frame.
+        let mut reader = &frames[..];
+        assert!(read_framed::<[u8; 32], _>(&mut reader, MAX_LENGTH).is_err());
+        assert!(read_framed::<[u8; 16], _>(&mut reader, MAX_LENGTH).is_err());
+        assert!(read_framed::<[u8; 2], _>(&mut reader, MAX_LENGTH).is_err());
+        assert_eq!(read_framed::<[u8; 32], _>(&mut reader, MAX_LENGTH).unwrap(), [3u8; 32]);
+        assert!(reader.is_empty());
+    }
+
+    /// A reader that returns at most one byte per call, as a socket may.
+    struct TrickleReader<'a>(&'a [u8]);
+
+    impl<'a> Read for TrickleReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((byte, rest)), Some(slot)) => {
+                    *slot = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_framed_short_reads() {
+        let expected = [42u64; 32];
+
+        let mut frame = vec![];
+        write_framed(&expected, &mut frame).unwrap();
+        assert_eq!(&frame[..3], &[0xfd, 0x00, 0x01]);
+
+        let candidate: [u64; 32] = read_framed(TrickleReader(&frame), MAX_LENGTH).unwrap();
+        assert_eq!(&expected[..], &candidate[..]);
+    }
+
+    #[test]
+    fn test_framed_length_prefix() {
+        // An empty stream is an error, not a zero-length frame.
+        assert!(read_framed::<(), _>(&[][..], MAX_LENGTH).is_err());
+
+        // A truncated length prefix is an error.
+        assert!(read_framed::<(), _>(&[0xfd, 0x00][..], MAX_LENGTH).is_err());
+
+        // A non-minimal length prefix is an error.
+        let mut frame = vec![0xfd, 0x01, 0x00, 0x07];
+        frame.extend_from_slice(&crc32fast::hash(&[0x07]).to_le_bytes());
+        let error = read_framed::<u8, _>(&frame[..], MAX_LENGTH).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid variable size integer");
+    }
+
+    #[test]
+    fn test_framed_unstable_length() {
+        /// A value whose serialized length grows on every write.
+        struct Unstable(std::cell::Cell<usize>);
+
+        impl ToBytes for Unstable {
+            fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+                self.0.set(self.0.get() + 1);
+                writer.write_all(&vec![0u8; self.0.get()])
+            }
+        }
+
+        let mut frame = vec![];
+        assert!(write_framed(&Unstable(Default::default()), &mut frame).is_err());
+    }
+
+    #[test]
+    fn test_framed_checksum_mismatch() {
+        let mut frame = vec![];
+        write_framed(&[1u8; 32], &mut frame).unwrap();
+        frame[10] ^= 1;
+
+        assert!(read_framed::<[u8; 32], _>(&frame[..], MAX_LENGTH).is_err());
+    }
+
+    #[test]
+    fn test_framed_trailing_bytes() {
+        let mut frame = vec![];
+        write_framed(&[1u8; 32], &mut frame).unwrap();
+
+        assert!(read_framed::<[u8; 16], _>(&frame[..], MAX_LENGTH).is_err());
+    }
+
+    #[test]
+    fn test_framed_truncated_payload() {
+        let mut frame = vec![];
+        write_framed(&[1u8; 16], &mut frame).unwrap();
+
+        assert!(read_framed::<[u8; 32], _>(&frame[..], MAX_LENGTH).is_err());
+    }
+
+    #[test]
+    fn test_framed_max_length() {
+        let mut frame = vec![];
+        write_framed(&[1u8; 32], &mut frame).unwrap();
+
+        assert!(read_framed::<[u8; 32], _>(&frame[..], 31).is_err());
+        assert!(read_framed::<[u8; 32], _>(&frame[..], 32).is_ok());
+    }
+
+    #[test]
+    fn test_buffered_round_trip() {
+        let expected = [9u32; 32];
+
+        let mut bytes = vec![];
+        write_buffered(&expected, &mut bytes).unwrap();
+        assert_eq!(bytes.len(), 128);
+
+        let candidate: [u32; 32] = read_buffered(&bytes[..]).unwrap();
+        assert_eq!(expected, candidate);
+    }
+
+    /// A value that serializes to `Blob::LENGTH` bytes, large enough to need a four-byte length prefix.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Blob(Vec<u8>);
+
+    impl Blob {
+        const LENGTH: usize = 300_000;
+
+        fn new() -> Self {
+            Blob((0..Self::LENGTH).map(|i| i as u8).collect())
+        }
+    }
+
+    impl ToBytes for Blob {
+        fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+            writer.write_all(&self.0)
+        }
+    }
+
+    impl FromBytes for Blob {
+        fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+            let mut bytes = vec![0u8; Self::LENGTH];
+            reader.read_exact(&mut bytes)?;
+            Ok(Blob(bytes))
+        }
+    }
+
+    #[test]
+    fn test_framed_large_round_trip() {
+        let mut frame = vec![];
+        write_framed(&Blob::new(), &mut frame).unwrap();
+        assert_eq!(frame.len(), 5 + Blob::LENGTH + 4);
+
+        let candidate: Blob = read_framed(TrickleReader(&frame), MAX_LENGTH).unwrap();
+        assert_eq!(candidate, Blob::new());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_framed_async_round_trip() {
+        use std::sync::Arc;
+
+        let expected = [42u64; 32];
+
+        let mut frames = vec![];
+        write_framed_async(Arc::new(expected), &mut frames).await.unwrap();
+        write_framed_async(Arc::new(300u16), &mut frames).await.unwrap();
+
+        // Frames written asynchronously are readable synchronously, and vice versa.
+        let mut sync_frames = vec![];
+        write_framed(&expected, &mut sync_frames).unwrap();
+        assert_eq!(&frames[..sync_frames.len()], &sync_frames[..]);
+
+        let mut reader = &frames[..];
+        let candidate: [u64; 32] = read_framed_async(&mut reader, MAX_LENGTH).await.unwrap();
+        assert_eq!(&expected[..], &candidate[..]);
+        assert_eq!(read_framed_async::<u16, _>(&mut reader, MAX_LENGTH).await.unwrap(), 300);
+        assert!(reader.is_empty());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_framed_async_streaming() {
+        use std::sync::Arc;
+
+        // The pipe holds far less than one frame, so both sides must stream concurrently.
+        let (mut client, mut server) = tokio::io::duplex(1024);
+
+        let writer = async {
+            write_framed_async(Arc::new(Blob::new()), &mut client).await.unwrap();
+            write_framed_async(Arc::new(7u8), &mut client).await.unwrap();
+        };
+        let reader = async {
+            let blob: Blob = read_framed_async(&mut server, MAX_LENGTH).await.unwrap();
+            let byte: u8 = read_framed_async(&mut server, MAX_LENGTH).await.unwrap();
+            (blob, byte)
+        };
+
+        let ((), (blob, byte)) = tokio::join!(writer, reader);
+        assert_eq!(blob, Blob::new());
+        assert_eq!(byte, 7);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_framed_async_checksum_mismatch() {
+        let mut frames = vec![];
+        write_framed(&[1u8; 32], &mut frames).unwrap();
+        write_framed(&[2u8; 32], &mut frames).unwrap();
+        frames[10] ^= 1;
+
+        let mut reader = &frames[..];
+        assert!(read_framed_async::<[u8; 32], _>(&mut reader, MAX_LENGTH).await.is_err());
+        assert_eq!(
+            read_framed_async::<[u8; 32], _>(&mut reader, MAX_LENGTH).await.unwrap(),
+            [2u8; 32]
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_framed_async_unbounded_max_length() {
+        // A maximal length prefix must not overflow when the checksum size is added to it.
+        let mut frame = vec![0xff];
+        frame.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(read_framed_async::<Blob, _>(&mut &frame[..], u64::MAX).await.is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_framed_async_writer_error() {
+        use std::{
+            io::ErrorKind,
+            pin::Pin,
+            sync::Arc,
+            task::{Context, Poll},
+            time::Duration,
+        };
+        use tokio::io::AsyncWrite;
+
+        /// A writer that accepts `remaining` bytes and then fails every write.
+        struct FailingWriter {
+            remaining: usize,
+        }
+
+        impl AsyncWrite for FailingWriter {
+            fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+                let writer = self.get_mut();
+                if writer.remaining == 0 {
+                    return Poll::Ready(Err(io::Error::new(ErrorKind::BrokenPipe, "writer closed")));
+                }
+
+                let amount = std::cmp::min(buf.len(), writer.remaining);
+                writer.remaining -= amount;
+                Poll::Ready(Ok(amount))
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<IoResult<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<IoResult<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        // The value is larger than the chunks that can be in flight, so the
+        // serialization task must observe the closed channel and stop.
+        let value = Arc::new(vec![Blob::new(); 4]);
+        let mut writer = FailingWriter { remaining: 1000 };
+
+        let result = tokio::time::timeout(Duration::from_secs(10), write_framed_async(value, &mut writer)).await;
+        let error = result.expect("write_framed_async hung").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+        assert_eq!(error.to_string(), "writer closed");
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_framed_async_truncated() {
+        let mut frame = vec![];
+        write_framed(&Blob::new(), &mut frame).unwrap();
+        frame.truncate(frame.len() - 100);
+
+        assert!(read_framed_async::<Blob, _>(&mut &frame[..], MAX_LENGTH).await.is_err());
+    }
+}
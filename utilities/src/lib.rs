@@ -18,6 +18,8 @@ pub mod biginteger;
 pub mod bititerator;
 #[macro_use]
 pub mod bytes;
+#[cfg(feature = "framing")]
+pub mod framing;
 pub mod rand;
 pub mod variable_length_integer;
 